@@ -0,0 +1,192 @@
+use anyhow::Context;
+use lhm_client::{HardwareType, SensorType};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Name of the config file loaded from the plugin's working directory
+const CONFIG_FILE_NAME: &str = "indicators.json";
+
+/// Matcher used to resolve a single sensor from the monitoring service.
+///
+/// Matchers are tried in the order they appear within an [`IndicatorConfig`],
+/// allowing a fallback list of candidates for machines where the expected
+/// hardware or sensor name differs (e.g. "CPU Package" vs "Core (Tctl/Tdie)").
+#[derive(Debug, Clone, Deserialize)]
+pub struct SensorMatcher {
+    /// Hardware type to search within, `None` searches all hardware
+    pub hardware_type: Option<HardwareType>,
+    /// Optional substring the hardware name must contain
+    pub hardware_name: Option<String>,
+    /// Sensor type to search for (e.g. temperature, load, power)
+    pub sensor_type: SensorType,
+    /// Ordered list of sensor name substrings to try, first match wins
+    pub sensor_names: Vec<String>,
+}
+
+/// A named thermal state entered once the sensor value reaches `min`.
+///
+/// Thresholds are declared ascending by `min`; the active state is the
+/// last threshold whose `min` the current value has reached.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ThermalThreshold {
+    /// Name of the state, e.g. "Normal", "Warm", "Hot", "Critical"
+    pub state: String,
+    /// Minimum value (inclusive) at which this state applies
+    pub min: f32,
+}
+
+/// Declaration of a single indicator exposed to displays
+#[derive(Debug, Clone, Deserialize)]
+pub struct IndicatorConfig {
+    /// Unique identifier for this indicator, used in display messages
+    pub id: String,
+    /// Human-readable label for the indicator
+    pub label: String,
+    /// Ordered matchers tried in turn to resolve the underlying sensor
+    pub matchers: Vec<SensorMatcher>,
+    /// Kind of sensor this indicator reports, carried to displays so a
+    /// single message shape can render temperature, load, power, etc.
+    pub sensor_type: SensorType,
+    /// Unit the value is expressed in (e.g. "°C", "%", "W", "RPM")
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Interval between polls of the sensor for subscribers that do not
+    /// request their own `min_interval_ms`; while subscribed, the sensor is
+    /// polled at the smallest interval any subscriber requested
+    #[serde(default = "default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+    /// Ascending thermal state thresholds, empty disables thermal states
+    #[serde(default)]
+    pub thresholds: Vec<ThermalThreshold>,
+    /// Margin the value must cross past a threshold before the state
+    /// changes, damping flapping for values that sit on a boundary
+    #[serde(default = "default_hysteresis")]
+    pub hysteresis: f32,
+    /// Number of recent samples to retain for history/sparkline rendering
+    #[serde(default = "default_history_capacity")]
+    pub history_capacity: usize,
+}
+
+fn default_refresh_interval_ms() -> u64 {
+    1000
+}
+
+fn default_hysteresis() -> f32 {
+    2.0
+}
+
+fn default_history_capacity() -> usize {
+    120
+}
+
+/// Root of the deserialized plugin configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginConfig {
+    /// Indicators to expose to displays
+    #[serde(default = "default_indicators")]
+    pub indicators: Vec<IndicatorConfig>,
+}
+
+/// Load the plugin config from the working directory, falling back to the
+/// built-in CPU/GPU temperature indicators when the file is absent
+pub fn load_config() -> anyhow::Result<PluginConfig> {
+    load_config_from(Path::new(CONFIG_FILE_NAME))
+}
+
+fn load_config_from(path: &Path) -> anyhow::Result<PluginConfig> {
+    if !path.exists() {
+        tracing_fallback();
+        return Ok(PluginConfig {
+            indicators: default_indicators(),
+        });
+    }
+
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("failed to read {path:?}"))?;
+
+    let config: PluginConfig =
+        serde_json::from_str(&contents).context("failed to parse indicator config")?;
+
+    Ok(config)
+}
+
+fn tracing_fallback() {
+    tilepad_plugin_sdk::tracing::warn!(
+        file = CONFIG_FILE_NAME,
+        "indicator config not found, using default CPU/GPU temperature indicators"
+    );
+}
+
+/// Default indicators matching the hardcoded CPU Package / GPU Core behavior
+pub(crate) fn default_indicators() -> Vec<IndicatorConfig> {
+    vec![
+        IndicatorConfig {
+            id: "cpu".to_string(),
+            label: "CPU Temperature".to_string(),
+            sensor_type: SensorType::Temperature,
+            unit: Some("°C".to_string()),
+            refresh_interval_ms: default_refresh_interval_ms(),
+            matchers: vec![SensorMatcher {
+                hardware_type: Some(HardwareType::Cpu),
+                hardware_name: None,
+                sensor_type: SensorType::Temperature,
+                sensor_names: vec!["CPU Package".to_string()],
+            }],
+            thresholds: default_temperature_thresholds(),
+            hysteresis: default_hysteresis(),
+            history_capacity: default_history_capacity(),
+        },
+        IndicatorConfig {
+            id: "gpu".to_string(),
+            label: "GPU Temperature".to_string(),
+            sensor_type: SensorType::Temperature,
+            unit: Some("°C".to_string()),
+            refresh_interval_ms: default_refresh_interval_ms(),
+            matchers: vec![
+                SensorMatcher {
+                    hardware_type: Some(HardwareType::GpuNvidia),
+                    hardware_name: None,
+                    sensor_type: SensorType::Temperature,
+                    sensor_names: vec!["GPU Core".to_string()],
+                },
+                SensorMatcher {
+                    hardware_type: Some(HardwareType::GpuAmd),
+                    hardware_name: None,
+                    sensor_type: SensorType::Temperature,
+                    sensor_names: vec!["GPU Core".to_string()],
+                },
+                SensorMatcher {
+                    hardware_type: Some(HardwareType::GpuIntel),
+                    hardware_name: None,
+                    sensor_type: SensorType::Temperature,
+                    sensor_names: vec!["GPU Core".to_string()],
+                },
+            ],
+            thresholds: default_temperature_thresholds(),
+            hysteresis: default_hysteresis(),
+            history_capacity: default_history_capacity(),
+        },
+    ]
+}
+
+/// Default Normal/Warm/Hot/Critical temperature thresholds
+fn default_temperature_thresholds() -> Vec<ThermalThreshold> {
+    vec![
+        ThermalThreshold {
+            state: "Normal".to_string(),
+            min: f32::MIN,
+        },
+        ThermalThreshold {
+            state: "Warm".to_string(),
+            min: 60.0,
+        },
+        ThermalThreshold {
+            state: "Hot".to_string(),
+            min: 80.0,
+        },
+        ThermalThreshold {
+            state: "Critical".to_string(),
+            min: 90.0,
+        },
+    ]
+}
@@ -2,6 +2,7 @@ use plugin::ExamplePlugin;
 use tilepad_plugin_sdk::{setup_tracing, start_plugin};
 use tokio::task::LocalSet;
 
+pub mod config;
 pub mod plugin;
 
 #[tokio::main(flavor = "current_thread")]
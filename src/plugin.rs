@@ -1,50 +1,109 @@
+use crate::config::{self, IndicatorConfig, PluginConfig, ThermalThreshold};
 use anyhow::Context;
-use lhm_client::{ComputerOptions, HardwareType, LHMClient, LHMClientHandle, Sensor, SensorType};
+use lhm_client::{ComputerOptions, LHMClient, LHMClientHandle, Sensor, SensorType};
 use serde::{Deserialize, Serialize};
-use std::{cell::Cell, rc::Rc, time::Duration};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use tilepad_plugin_sdk::{Plugin, PluginSessionHandle, tracing};
 use tokio::{
     sync::Mutex,
-    task::{JoinHandle, spawn_local},
+    task::{AbortHandle, spawn_local},
     time::sleep,
-    try_join,
 };
 
-#[derive(Default)]
 pub struct IndicatorsPlugin {
+    /// Indicator definitions loaded from the plugin config
+    config: Rc<PluginConfig>,
+
     client_handle: Rc<ManagedClient>,
 
-    /// Current CPU temperature value
-    cpu_value: Rc<Cell<f32>>,
-    /// Handle for the task managing CPU requests
-    cpu_task: Option<JoinHandle<()>>,
+    /// Shared cache of the most recently polled sensor values
+    cache: Rc<SensorCache>,
+
+    /// Bounded recent sample history per indicator, for sparklines/graphs
+    history: Rc<HistoryStore>,
+
+    /// Displays currently subscribed to each indicator's updates
+    subscriptions: Rc<Subscriptions>,
+}
+
+impl Default for IndicatorsPlugin {
+    fn default() -> Self {
+        let config = config::load_config().unwrap_or_else(|cause| {
+            tracing::error!(?cause, "failed to load indicator config, using defaults");
+            PluginConfig {
+                indicators: config::default_indicators(),
+            }
+        });
+
+        Self {
+            config: Rc::new(config),
+            client_handle: Default::default(),
+            cache: Default::default(),
+            history: Default::default(),
+            subscriptions: Default::default(),
+        }
+    }
+}
 
-    /// Current GPU temperature value
-    gpu_value: Rc<Cell<f32>>,
-    /// Handle for the task managing GPU requests
-    gpu_task: Option<JoinHandle<()>>,
+/// Find an indicator definition by its configured identifier
+fn find_indicator<'a>(config: &'a PluginConfig, id: &str) -> Option<&'a IndicatorConfig> {
+    config.indicators.iter().find(|indicator| indicator.id == id)
 }
 
 /// Message from the display
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 enum DisplayMessageIn {
-    GetCpuTemp { nonce: u32 },
-    GetGpuTemp { nonce: u32 },
+    Subscribe {
+        sensor: String,
+        min_interval_ms: Option<u64>,
+    },
+    Unsubscribe {
+        sensor: String,
+    },
+    GetHistory {
+        sensor: String,
+        max_points: usize,
+    },
 }
 
 /// Message sent to the display
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 enum DisplayMessageOut {
-    CpuTemp { value: f32, nonce: u32 },
-    GpuTemp { value: f32, nonce: u32 },
+    SensorChanged {
+        sensor: String,
+        sensor_type: SensorType,
+        unit: Option<String>,
+        value: f32,
+    },
+    ThermalState {
+        sensor: String,
+        state: String,
+        value: f32,
+    },
+    HistoryPoints {
+        sensor: String,
+        points: Vec<(u64, f32)>,
+    },
+    SensorUnavailable {
+        sensor: String,
+        reason: String,
+    },
+    SensorRestored {
+        sensor: String,
+    },
 }
 
 impl Plugin for IndicatorsPlugin {
     fn on_display_message(
         &mut self,
-        _session: &PluginSessionHandle,
+        session: &PluginSessionHandle,
         display: tilepad_plugin_sdk::Display,
         message: serde_json::Value,
     ) {
@@ -57,37 +116,56 @@ impl Plugin for IndicatorsPlugin {
         };
 
         match message {
-            DisplayMessageIn::GetCpuTemp { nonce } => {
-                // No client handle is initialized
-
-                // Initialize the background task on first request
-                if self.cpu_task.is_none() {
-                    let task = spawn_local(run_cpu_sensor(
+            DisplayMessageIn::Subscribe {
+                sensor,
+                min_interval_ms,
+            } => {
+                let Some(indicator) = find_indicator(&self.config, &sensor).cloned() else {
+                    tracing::warn!(indicator = %sensor, "display subscribed to unknown indicator");
+                    return;
+                };
+
+                let min_interval = min_interval_ms
+                    .map(Duration::from_millis)
+                    .unwrap_or_else(|| Duration::from_millis(indicator.refresh_interval_ms));
+
+                let is_first_subscriber = self.subscriptions.subscribe(
+                    &sensor,
+                    session.clone(),
+                    display.clone(),
+                    min_interval,
+                );
+
+                if is_first_subscriber {
+                    let task = spawn_local(run_push_loop(
+                        sensor.clone(),
                         self.client_handle.clone(),
-                        self.cpu_value.clone(),
+                        self.cache.clone(),
+                        self.history.clone(),
+                        indicator.clone(),
+                        self.subscriptions.clone(),
                     ));
-                    self.cpu_task = Some(task);
+                    self.subscriptions.set_task(&sensor, task.abort_handle());
                 }
 
-                // Get the current value and send it back to the display
-                let value = self.cpu_value.get();
-                _ = display.send(DisplayMessageOut::CpuTemp { value, nonce });
-            }
-            DisplayMessageIn::GetGpuTemp { nonce } => {
-                // No client handle is initialized
+                // Give the new subscriber an immediate snapshot instead of
+                // making it wait for the shared loop's next broadcast
+                if let Some(reason) = self.cache.last_error(&sensor) {
+                    _ = display.send(DisplayMessageOut::SensorUnavailable { sensor, reason });
+                } else if let Some(value) = self.cache.get(&sensor) {
+                    _ = display.send(sensor_changed_message(&indicator, value));
 
-                // Initialize the background task on first request
-                if self.gpu_task.is_none() {
-                    let task = spawn_local(run_gpu_sensor(
-                        self.client_handle.clone(),
-                        self.gpu_value.clone(),
-                    ));
-                    self.gpu_task = Some(task);
+                    if let Some(state) = self.cache.last_thermal_state(&sensor) {
+                        _ = display.send(DisplayMessageOut::ThermalState { sensor, state, value });
+                    }
                 }
-
-                // Get the current value and send it back to the display
-                let value = self.gpu_value.get();
-                _ = display.send(DisplayMessageOut::GpuTemp { value, nonce });
+            }
+            DisplayMessageIn::Unsubscribe { sensor } => {
+                self.subscriptions.unsubscribe(&sensor, session);
+            }
+            DisplayMessageIn::GetHistory { sensor, max_points } => {
+                let points = self.history.get(&sensor, max_points);
+                _ = display.send(DisplayMessageOut::HistoryPoints { sensor, points });
             }
         }
     }
@@ -145,188 +223,602 @@ impl ManagedClient {
     }
 }
 
-// Find a sensor for the current CPU
-async fn get_cpu_sensor(client: &LHMClientHandle) -> anyhow::Result<Sensor> {
-    // Query for CPU hardware
-    let cpu_hardware = client.query_hardware(None, Some(HardwareType::Cpu)).await?;
-
-    // Get the first CPU hardware
-    let cpu = cpu_hardware
-        .into_iter()
-        .next()
-        .context("missing cpu hardware")?;
-
-    // Query the cpu hardware for temperature sensors
-    let sensors = client
-        .query_sensors(Some(cpu.identifier), Some(SensorType::Temperature))
-        .await?;
-
-    // Get the sensor for the CPU Package
-    let sensor = sensors
-        .into_iter()
-        .find(|sensor| sensor.name.eq("CPU Package"))
-        .context("missing cpu sensor")?;
-
-    Ok(sensor)
+/// A single polled reading, cached alongside when it was observed
+struct CacheEntry {
+    value: f32,
+    updated_at: Instant,
 }
 
-// Find a sensor for the current CPU
-async fn get_gpu_sensor(client: &LHMClientHandle) -> anyhow::Result<Sensor> {
-    // Query for GPU hardware
-    let (gpu_nvidia, gpu_amd, gpu_intel) = try_join!(
-        client.query_hardware(None, Some(HardwareType::GpuNvidia)),
-        client.query_hardware(None, Some(HardwareType::GpuAmd)),
-        client.query_hardware(None, Some(HardwareType::GpuIntel)),
-    )?;
-
-    // Get the first GPU hardware
-    let gpu = gpu_nvidia
-        .into_iter()
-        .chain(gpu_amd.into_iter())
-        .chain(gpu_intel.into_iter())
-        .next()
-        .context("missing gpu")?;
-
-    // Query the cpu hardware for temperature sensors
-    let sensors = client
-        .query_sensors(Some(gpu.identifier), Some(SensorType::Temperature))
-        .await?;
-
-    // Get the sensor for the CPU Package
-    let sensor = sensors
-        .into_iter()
-        .find(|sensor| sensor.name.eq("GPU Core"))
-        .context("missing gpu sensor")?;
-
-    Ok(sensor)
+/// Shared cache of polled sensor values, keyed by indicator identifier.
+///
+/// A failed refresh never overwrites a cached value: the previous value (or
+/// nothing) stays in place until the next attempt succeeds. The failure's
+/// reason is recorded separately until the indicator refreshes again.
+#[derive(Default)]
+struct SensorCache {
+    entries: RefCell<HashMap<String, CacheEntry>>,
+    resolved: RefCell<HashMap<String, Sensor>>,
+    thermal_states: RefCell<HashMap<String, String>>,
+    errors: RefCell<HashMap<String, String>>,
 }
 
-/// Run a loop for the CPU sensor storing its current temperature value in `cpu_value`
-async fn run_cpu_sensor(client: Rc<ManagedClient>, cpu_value: Rc<Cell<f32>>) {
-    let mut retry_attempt = 0;
+impl SensorCache {
+    /// Get the last cached value for an indicator, if any
+    fn get(&self, id: &str) -> Option<f32> {
+        self.entries.borrow().get(id).map(|entry| entry.value)
+    }
 
-    loop {
-        let client = match client.acquire().await {
-            Some(value) => value,
-            None => {
-                if retry_attempt > 3 {
-                    return;
-                }
+    /// Store a freshly polled value
+    fn set(&self, id: &str, value: f32) {
+        self.entries.borrow_mut().insert(
+            id.to_string(),
+            CacheEntry {
+                value,
+                updated_at: Instant::now(),
+            },
+        );
+    }
 
-                retry_attempt += 1;
-                // Wait before retrying
-                sleep(Duration::from_secs(5)).await;
-                continue;
-            }
-        };
+    /// Previously resolved sensor for `id`, if we have one cached
+    fn resolved_sensor(&self, id: &str) -> Option<Sensor> {
+        self.resolved.borrow().get(id).cloned()
+    }
+
+    /// Remember a resolved sensor so later refreshes skip re-matching it
+    fn set_resolved(&self, id: &str, sensor: Sensor) {
+        self.resolved.borrow_mut().insert(id.to_string(), sensor);
+    }
 
-        retry_attempt = 0;
+    /// Forget a resolved sensor, forcing the next refresh to re-match it
+    fn clear_resolved(&self, id: &str) {
+        self.resolved.borrow_mut().remove(id);
+    }
 
-        // Get the CPU sensor
-        let mut cpu_sensor = match get_cpu_sensor(&client).await {
-            Ok(value) => value,
-            Err(cause) => {
-                tracing::error!(?cause, "failed to obtain cpu sensor");
-                return;
+    /// Last thermal state emitted for an indicator, if any
+    fn last_thermal_state(&self, id: &str) -> Option<String> {
+        self.thermal_states.borrow().get(id).cloned()
+    }
+
+    /// Record the thermal state most recently emitted for an indicator
+    fn set_thermal_state(&self, id: &str, state: String) {
+        self.thermal_states.borrow_mut().insert(id.to_string(), state);
+    }
+
+    /// Reason an indicator is currently unavailable, if it is failing
+    fn last_error(&self, id: &str) -> Option<String> {
+        self.errors.borrow().get(id).cloned()
+    }
+
+    /// Record that an indicator is currently failing to refresh
+    fn set_error(&self, id: &str, reason: String) {
+        self.errors.borrow_mut().insert(id.to_string(), reason);
+    }
+
+    /// Clear an indicator's failing state once it refreshes successfully
+    fn clear_error(&self, id: &str) {
+        self.errors.borrow_mut().remove(id);
+    }
+}
+
+/// Resolve the sensor for an indicator by walking its matchers in order,
+/// returning the first sensor that satisfies a matcher's fallback names
+async fn resolve_sensor(
+    client: &LHMClientHandle,
+    indicator: &IndicatorConfig,
+) -> anyhow::Result<Sensor> {
+    for matcher in &indicator.matchers {
+        let hardware = client
+            .query_hardware(None, matcher.hardware_type.clone())
+            .await?;
+
+        let hardware = hardware.into_iter().filter(|hw| {
+            matcher
+                .hardware_name
+                .as_deref()
+                .is_none_or(|name| hw.name.contains(name))
+        });
+
+        for hardware in hardware {
+            let mut sensors = client
+                .query_sensors(Some(hardware.identifier), Some(matcher.sensor_type.clone()))
+                .await?;
+
+            for pattern in &matcher.sensor_names {
+                if let Some(position) = sensors
+                    .iter()
+                    .position(|sensor| sensor.name.contains(pattern.as_str()))
+                {
+                    return Ok(sensors.swap_remove(position));
+                }
             }
+        }
+    }
+
+    anyhow::bail!("no sensor matched indicator `{}`", indicator.id)
+}
+
+async fn try_refresh(
+    client: &ManagedClient,
+    cache: &SensorCache,
+    indicator: &IndicatorConfig,
+) -> anyhow::Result<()> {
+    let lhm_client = client
+        .acquire()
+        .await
+        .context("monitoring service unavailable")?;
+
+    let sensor = match cache.resolved_sensor(&indicator.id) {
+        Some(sensor) => sensor,
+        None => {
+            let sensor = resolve_sensor(&lhm_client, indicator).await?;
+            cache.set_resolved(&indicator.id, sensor.clone());
+            sensor
+        }
+    };
+
+    let value = match lhm_client
+        .get_sensor_value_by_id(sensor.identifier.clone(), true)
+        .await?
+    {
+        Some(value) => value,
+        None => {
+            // Sensor disappeared (e.g. another client refreshed hardware), re-resolve once
+            cache.clear_resolved(&indicator.id);
+            let sensor = resolve_sensor(&lhm_client, indicator).await?;
+            let value = lhm_client
+                .get_sensor_value_by_id(sensor.identifier.clone(), true)
+                .await?
+                .context("sensor value unavailable after re-resolving")?;
+            cache.set_resolved(&indicator.id, sensor);
+            value
+        }
+    };
+
+    cache.set(&indicator.id, value);
+    Ok(())
+}
+
+/// Current time as milliseconds since the Unix epoch
+fn epoch_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Bounded per-indicator sample history, for sparkline/graph rendering.
+///
+/// Each series is capped at the indicator's configured capacity, evicting
+/// the oldest sample as new ones arrive so memory stays bounded regardless
+/// of how long the plugin has been running.
+#[derive(Default)]
+struct HistoryStore {
+    series: RefCell<HashMap<String, VecDeque<(u64, f32)>>>,
+}
+
+impl HistoryStore {
+    /// Record a sample for `id`, evicting the oldest once `capacity` is exceeded
+    fn push(&self, id: &str, timestamp_ms: u64, value: f32, capacity: usize) {
+        let mut series = self.series.borrow_mut();
+        let samples = series.entry(id.to_string()).or_default();
+        samples.push_back((timestamp_ms, value));
+        while samples.len() > capacity {
+            samples.pop_front();
+        }
+    }
+
+    /// Return up to `max_points` samples for `id`, oldest first, averaging
+    /// into evenly sized buckets when more samples are stored than requested
+    fn get(&self, id: &str, max_points: usize) -> Vec<(u64, f32)> {
+        let series = self.series.borrow();
+        let Some(samples) = series.get(id) else {
+            return Vec::new();
         };
 
-        'client: loop {
-            // Get the current value of the CPUs sensor
-            let value = match client
-                .get_sensor_value_by_id(cpu_sensor.identifier.clone(), true)
-                .await
-            {
-                Ok(Some(value)) => value,
-                // CPU sensor was lost (Another client refreshed cache?)
-                Ok(None) => {
-                    // Try and obtain the CPU sensor again
-                    if let Ok(value) = get_cpu_sensor(&client).await {
-                        cpu_sensor = value;
-                        continue;
-                    }
+        if max_points == 0 || samples.is_empty() {
+            return Vec::new();
+        }
 
-                    // Some other factor is preventing us from gaining the CPU sensor
-                    tracing::warn!("cpu temperature sensor no longer exists");
-                    return;
-                }
+        if samples.len() <= max_points {
+            return samples.iter().copied().collect();
+        }
 
-                Err(cause) => {
-                    tracing::error!(?cause, "failed to get current temperature value");
-                    break 'client;
-                }
-            };
+        let bucket_size = samples.len().div_ceil(max_points);
+        samples
+            .iter()
+            .copied()
+            .collect::<Vec<_>>()
+            .chunks(bucket_size)
+            .map(|bucket| {
+                let timestamp_ms = bucket[bucket.len() - 1].0;
+                let average = bucket.iter().map(|(_, value)| value).sum::<f32>() / bucket.len() as f32;
+                (timestamp_ms, average)
+            })
+            .collect()
+    }
+}
 
-            // Update the current temperature value
-            cpu_value.set(value);
+/// A single display subscribed to an indicator's updates
+struct Subscriber {
+    display: tilepad_plugin_sdk::Display,
+    min_interval: Duration,
+}
 
-            // Wait till the next tick
-            sleep(Duration::from_secs(1)).await;
+/// An indicator's current subscribers, plus a handle to abort the push task
+/// serving them while at least one subscriber remains
+#[derive(Default)]
+struct Subscription {
+    subscribers: HashMap<PluginSessionHandle, Subscriber>,
+    task: Option<AbortHandle>,
+}
+
+/// Registry of hanging-get subscriptions, keyed by indicator identifier
+#[derive(Default)]
+struct Subscriptions {
+    inner: RefCell<HashMap<String, Subscription>>,
+}
+
+impl Subscriptions {
+    /// Register `display` as subscribed to `id`, returning `true` if it is
+    /// the first subscriber so the caller can start the push loop
+    fn subscribe(
+        &self,
+        id: &str,
+        session: PluginSessionHandle,
+        display: tilepad_plugin_sdk::Display,
+        min_interval: Duration,
+    ) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let subscription = inner.entry(id.to_string()).or_default();
+        let is_first = subscription.subscribers.is_empty();
+        subscription
+            .subscribers
+            .insert(session, Subscriber { display, min_interval });
+        is_first
+    }
+
+    /// Remove a display's subscription to `id`
+    fn unsubscribe(&self, id: &str, session: &PluginSessionHandle) {
+        if let Some(subscription) = self.inner.borrow_mut().get_mut(id) {
+            subscription.subscribers.remove(session);
+        }
+    }
+
+    /// Whether `id` currently has no subscribers
+    fn is_empty(&self, id: &str) -> bool {
+        self.inner
+            .borrow()
+            .get(id)
+            .is_none_or(|subscription| subscription.subscribers.is_empty())
+    }
+
+    /// Smallest interval requested across all current subscribers of `id`
+    fn min_interval(&self, id: &str) -> Option<Duration> {
+        self.inner.borrow().get(id).and_then(|subscription| {
+            subscription
+                .subscribers
+                .values()
+                .map(|subscriber| subscriber.min_interval)
+                .min()
+        })
+    }
+
+    /// Send `message` to every current subscriber of `id`
+    fn broadcast(&self, id: &str, message: DisplayMessageOut) {
+        if let Some(subscription) = self.inner.borrow().get(id) {
+            for subscriber in subscription.subscribers.values() {
+                _ = subscriber.display.send(message.clone());
+            }
+        }
+    }
+
+    /// Record the task currently pushing updates for `id`, aborting whatever
+    /// task was previously registered so unsubscribe-then-resubscribe churn
+    /// can never leave two push loops running for the same indicator
+    fn set_task(&self, id: &str, task: AbortHandle) {
+        let mut inner = self.inner.borrow_mut();
+        let subscription = inner.entry(id.to_string()).or_default();
+        if let Some(previous) = subscription.task.replace(task) {
+            previous.abort();
+        }
+    }
+
+    /// Clear the push task for `id`, allowing a future subscriber to restart it
+    fn clear_task(&self, id: &str) {
+        if let Some(subscription) = self.inner.borrow_mut().get_mut(id) {
+            subscription.task = None;
         }
     }
 }
 
-/// Run a loop for the GPU sensor storing its current temperature value in `gpu_value`
-async fn run_gpu_sensor(client: Rc<ManagedClient>, gpu_value: Rc<Cell<f32>>) {
-    let mut retry_attempt = 0;
+/// How often to resend the current value even when it hasn't changed, so a
+/// freshly (re)connected display knows the feed is alive
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Minimum change in value before it is considered worth pushing to displays
+const CHANGE_EPSILON: f32 = 0.01;
+
+/// Starting delay for the reconnect backoff after a refresh failure
+const BACKOFF_BASE: Duration = Duration::from_secs(1);
+
+/// Ceiling for the reconnect backoff, so a dead monitoring service is
+/// retried periodically rather than abandoned
+const BACKOFF_MAX: Duration = Duration::from_secs(60);
+
+/// Exponential backoff delay for the given number of consecutive failures,
+/// capped at `BACKOFF_MAX` so retries continue indefinitely rather than
+/// giving up once a display is still subscribed
+fn backoff_delay(consecutive_failures: u32) -> Duration {
+    let factor = 1u32.checked_shl(consecutive_failures.min(6)).unwrap_or(u32::MAX);
+    BACKOFF_BASE.saturating_mul(factor).min(BACKOFF_MAX)
+}
+
+/// Build the outgoing "value changed" message for an indicator
+fn sensor_changed_message(indicator: &IndicatorConfig, value: f32) -> DisplayMessageOut {
+    DisplayMessageOut::SensorChanged {
+        sensor: indicator.id.clone(),
+        sensor_type: indicator.sensor_type.clone(),
+        unit: indicator.unit.clone(),
+        value,
+    }
+}
+
+/// Classify `value` into one of `thresholds`, applying `hysteresis` so a
+/// value sitting on a boundary doesn't flap between adjacent states.
+///
+/// The candidate state is the last threshold whose `min` the value has
+/// reached. Rather than jumping straight to the candidate, the state walks
+/// one band at a time from `previous`, checking each boundary's own margin
+/// in turn, so a multi-band swing in a single tick (e.g. a reconnect, or a
+/// fast thermal spike) still settles as far as the margins allow instead of
+/// getting stuck comparing only against the final band's threshold.
+fn classify_thermal_state<'a>(
+    thresholds: &'a [ThermalThreshold],
+    value: f32,
+    previous: Option<&str>,
+    hysteresis: f32,
+) -> Option<&'a str> {
+    if thresholds.is_empty() {
+        return None;
+    }
+
+    let candidate_index = thresholds
+        .iter()
+        .rposition(|threshold| value >= threshold.min)
+        .unwrap_or(0);
+
+    let Some(previous) = previous else {
+        return Some(thresholds[candidate_index].state.as_str());
+    };
+
+    let Some(mut index) = thresholds
+        .iter()
+        .position(|threshold| threshold.state == previous)
+    else {
+        return Some(thresholds[candidate_index].state.as_str());
+    };
+
+    while index < candidate_index && value >= thresholds[index + 1].min + hysteresis {
+        index += 1;
+    }
+    while index > candidate_index && value < thresholds[index].min - hysteresis {
+        index -= 1;
+    }
+
+    Some(thresholds[index].state.as_str())
+}
+
+/// Poll an indicator on behalf of its subscribers for as long as any remain,
+/// pushing a change message whenever the value moves or the keepalive
+/// interval elapses, rather than waiting for displays to poll for it
+async fn run_push_loop(
+    id: String,
+    client: Rc<ManagedClient>,
+    cache: Rc<SensorCache>,
+    history: Rc<HistoryStore>,
+    indicator: IndicatorConfig,
+    subscriptions: Rc<Subscriptions>,
+) {
+    let mut last_sent: Option<f32> = None;
+    let mut last_sent_at = Instant::now() - KEEPALIVE_INTERVAL;
+    let mut consecutive_failures: u32 = 0;
 
     loop {
-        let client = match client.acquire().await {
-            Some(value) => value,
-            None => {
-                if retry_attempt > 3 {
-                    return;
+        if subscriptions.is_empty(&id) {
+            break;
+        }
+
+        let value = match try_refresh(&client, &cache, &indicator).await {
+            Ok(()) => {
+                if consecutive_failures > 0 {
+                    consecutive_failures = 0;
+                    cache.clear_error(&id);
+                    subscriptions.broadcast(
+                        &id,
+                        DisplayMessageOut::SensorRestored { sensor: id.clone() },
+                    );
+                }
+
+                cache.get(&id)
+            }
+            Err(cause) => {
+                tracing::error!(?cause, indicator = %id, "failed to poll sensor for subscribers");
+
+                if consecutive_failures == 0 {
+                    cache.set_error(&id, cause.to_string());
+                    subscriptions.broadcast(
+                        &id,
+                        DisplayMessageOut::SensorUnavailable {
+                            sensor: id.clone(),
+                            reason: cause.to_string(),
+                        },
+                    );
                 }
 
-                retry_attempt += 1;
-                // Wait before retrying
-                sleep(Duration::from_secs(5)).await;
-                continue;
+                consecutive_failures = consecutive_failures.saturating_add(1);
+                None
             }
         };
 
-        retry_attempt = 0;
+        if let Some(value) = value {
+            history.push(&id, epoch_ms(), value, indicator.history_capacity);
 
-        // Get the GPU sensor
-        let mut gpu_sensor = match get_gpu_sensor(&client).await {
-            Ok(value) => value,
-            Err(cause) => {
-                tracing::error!(?cause, "failed to obtain cpu sensor");
-                return;
+            let changed = last_sent.is_none_or(|prev| (prev - value).abs() > CHANGE_EPSILON);
+
+            if changed || last_sent_at.elapsed() >= KEEPALIVE_INTERVAL {
+                subscriptions.broadcast(&id, sensor_changed_message(&indicator, value));
+                last_sent = Some(value);
+                last_sent_at = Instant::now();
             }
+
+            let previous_state = cache.last_thermal_state(&id);
+            if let Some(state) = classify_thermal_state(
+                &indicator.thresholds,
+                value,
+                previous_state.as_deref(),
+                indicator.hysteresis,
+            ) {
+                if previous_state.as_deref() != Some(state) {
+                    cache.set_thermal_state(&id, state.to_string());
+                    subscriptions.broadcast(
+                        &id,
+                        DisplayMessageOut::ThermalState {
+                            sensor: id.clone(),
+                            state: state.to_string(),
+                            value,
+                        },
+                    );
+                }
+            }
+        }
+
+        let interval = if consecutive_failures > 0 {
+            backoff_delay(consecutive_failures - 1)
+        } else {
+            subscriptions
+                .min_interval(&id)
+                .unwrap_or_else(|| Duration::from_millis(indicator.refresh_interval_ms))
         };
+        sleep(interval).await;
+    }
 
-        'client: loop {
-            // Get the current value of the CPUs sensor
-            let value = match client
-                .get_sensor_value_by_id(gpu_sensor.identifier.clone(), true)
-                .await
-            {
-                Ok(Some(value)) => value,
-                // CPU sensor was lost (Another client refreshed cache?)
-                Ok(None) => {
-                    // Try and obtain the CPU sensor again
-                    if let Ok(value) = get_gpu_sensor(&client).await {
-                        gpu_sensor = value;
-                        continue;
-                    }
+    subscriptions.clear_task(&id);
+}
 
-                    // Some other factor is preventing us from gaining the CPU sensor
-                    tracing::warn!("cpu temperature sensor no longer exists");
-                    return;
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thresholds() -> Vec<ThermalThreshold> {
+        vec![
+            ThermalThreshold {
+                state: "Normal".to_string(),
+                min: f32::MIN,
+            },
+            ThermalThreshold {
+                state: "Warm".to_string(),
+                min: 60.0,
+            },
+            ThermalThreshold {
+                state: "Hot".to_string(),
+                min: 80.0,
+            },
+            ThermalThreshold {
+                state: "Critical".to_string(),
+                min: 90.0,
+            },
+        ]
+    }
 
-                Err(cause) => {
-                    tracing::error!(?cause, "failed to get current temperature value");
-                    break 'client;
-                }
-            };
+    #[test]
+    fn classify_thermal_state_no_previous_jumps_straight_to_candidate() {
+        let thresholds = thresholds();
+        assert_eq!(
+            classify_thermal_state(&thresholds, 81.0, None, 2.0),
+            Some("Hot")
+        );
+    }
+
+    #[test]
+    fn classify_thermal_state_multi_band_jump_leaves_previous_state() {
+        let thresholds = thresholds();
+        // A steady 81.0 reading is inside the Hot band, but falls short of
+        // Hot's own margin (80.0 + 2.0 hysteresis = 82.0) while clearing
+        // Warm's (60.0 + 2.0 = 62.0).
+        // It must still move at least one band up from Normal rather than
+        // getting stuck forever comparing only against the final candidate.
+        let state = classify_thermal_state(&thresholds, 81.0, Some("Normal"), 2.0);
+        assert_ne!(state, Some("Normal"));
+        assert_eq!(state, Some("Warm"));
+    }
 
-            // Update the current temperature value
-            gpu_value.set(value);
+    #[test]
+    fn classify_thermal_state_clears_full_margin_in_one_tick() {
+        let thresholds = thresholds();
+        // Comfortably past every remaining threshold's margin, so the walk
+        // should reach the true candidate band in a single call.
+        assert_eq!(
+            classify_thermal_state(&thresholds, 95.0, Some("Normal"), 2.0),
+            Some("Critical")
+        );
+    }
+
+    #[test]
+    fn classify_thermal_state_drops_through_multiple_bands() {
+        let thresholds = thresholds();
+        assert_eq!(
+            classify_thermal_state(&thresholds, -100.0, Some("Critical"), 2.0),
+            Some("Normal")
+        );
+    }
+
+    #[test]
+    fn classify_thermal_state_sticks_within_hysteresis_margin() {
+        let thresholds = thresholds();
+        // Dipping just below Warm's boundary, but within the hysteresis
+        // margin (60.0 - 2.0 = 58.0), shouldn't flap back to Normal.
+        assert_eq!(
+            classify_thermal_state(&thresholds, 59.0, Some("Warm"), 2.0),
+            Some("Warm")
+        );
+    }
 
-            // Wait till the next tick
-            sleep(Duration::from_secs(1)).await;
+    #[test]
+    fn history_store_returns_all_samples_when_under_capacity() {
+        let history = HistoryStore::default();
+        history.push("cpu", 1, 10.0, 10);
+        history.push("cpu", 2, 20.0, 10);
+
+        assert_eq!(history.get("cpu", 10), vec![(1, 10.0), (2, 20.0)]);
+    }
+
+    #[test]
+    fn history_store_evicts_oldest_beyond_capacity() {
+        let history = HistoryStore::default();
+        history.push("cpu", 1, 10.0, 2);
+        history.push("cpu", 2, 20.0, 2);
+        history.push("cpu", 3, 30.0, 2);
+
+        assert_eq!(history.get("cpu", 10), vec![(2, 20.0), (3, 30.0)]);
+    }
+
+    #[test]
+    fn history_store_downsamples_into_averaged_buckets() {
+        let history = HistoryStore::default();
+        for (timestamp_ms, value) in [(1, 10.0), (2, 20.0), (3, 30.0), (4, 40.0)] {
+            history.push("cpu", timestamp_ms, value, 10);
         }
+
+        // 4 samples requested as 2 points -> buckets of 2, tagged with each
+        // bucket's last timestamp and averaged value.
+        assert_eq!(history.get("cpu", 2), vec![(2, 15.0), (4, 35.0)]);
+    }
+
+    #[test]
+    fn backoff_delay_doubles_up_to_the_cap() {
+        assert_eq!(backoff_delay(0), Duration::from_secs(1));
+        assert_eq!(backoff_delay(1), Duration::from_secs(2));
+        assert_eq!(backoff_delay(2), Duration::from_secs(4));
+        assert_eq!(backoff_delay(10), BACKOFF_MAX);
     }
 }